@@ -0,0 +1,61 @@
+// A small embedded bitmap font for the framebuffer console. Each glyph is
+// authored as 7 rows of 5 pixels (in the left 5 columns of an 8-pixel-wide
+// cell, one byte per row with the pixel data in the high bits) and an
+// unused 8th row; `FramebufferConsole` doubles every row vertically to
+// fill a 16-scanline-tall cell. Lowercase letters are folded to their
+// uppercase glyph and anything outside the covered set renders blank -
+// this is a small hand-authored font, not a faithful reproduction of any
+// particular historical character ROM.
+//
+// Shared by `kernel` and `zkernel` (pulled in with `#[path]`, since there's
+// no Cargo workspace yet to declare a real path dependency on this crate) -
+// edit this copy, not one under either crate's `src/`.
+
+pub const GLYPH_WIDTH: usize = 8;
+pub const GLYPH_ROWS: usize = 8;
+
+pub fn glyph_rows(c: char) -> [u8; GLYPH_ROWS] {
+    match c.to_ascii_uppercase() {
+        '0' => [0x70, 0x88, 0x98, 0xA8, 0xC8, 0x88, 0x70, 0x00],
+        '1' => [0x20, 0x60, 0x20, 0x20, 0x20, 0x20, 0xF8, 0x00],
+        '2' => [0x70, 0x88, 0x08, 0x10, 0x20, 0x40, 0xF8, 0x00],
+        '3' => [0x70, 0x88, 0x08, 0x30, 0x08, 0x88, 0x70, 0x00],
+        '4' => [0x10, 0x30, 0x50, 0x90, 0xF8, 0x10, 0x10, 0x00],
+        '5' => [0xF8, 0x80, 0xF0, 0x08, 0x08, 0x88, 0x70, 0x00],
+        '6' => [0x30, 0x40, 0x80, 0xF0, 0x88, 0x88, 0x70, 0x00],
+        '7' => [0xF8, 0x08, 0x10, 0x20, 0x40, 0x40, 0x40, 0x00],
+        '8' => [0x70, 0x88, 0x88, 0x70, 0x88, 0x88, 0x70, 0x00],
+        '9' => [0x70, 0x88, 0x88, 0x78, 0x08, 0x10, 0x60, 0x00],
+        'A' => [0x70, 0x88, 0x88, 0xF8, 0x88, 0x88, 0x00, 0x00],
+        'B' => [0xF0, 0x88, 0x88, 0xF0, 0x88, 0x88, 0xF0, 0x00],
+        'C' => [0x78, 0x80, 0x80, 0x80, 0x80, 0x80, 0x78, 0x00],
+        'D' => [0xF0, 0x88, 0x88, 0x88, 0x88, 0x88, 0xF0, 0x00],
+        'E' => [0xF8, 0x80, 0x80, 0xF0, 0x80, 0x80, 0xF8, 0x00],
+        'F' => [0xF8, 0x80, 0x80, 0xF0, 0x80, 0x80, 0x80, 0x00],
+        'G' => [0x78, 0x80, 0x80, 0xB8, 0x88, 0x88, 0x78, 0x00],
+        'H' => [0x88, 0x88, 0x88, 0xF8, 0x88, 0x88, 0x88, 0x00],
+        'I' => [0xF8, 0x20, 0x20, 0x20, 0x20, 0x20, 0xF8, 0x00],
+        'J' => [0x38, 0x10, 0x10, 0x10, 0x10, 0x90, 0x60, 0x00],
+        'K' => [0x88, 0x90, 0xA0, 0xC0, 0xA0, 0x90, 0x88, 0x00],
+        'L' => [0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0xF8, 0x00],
+        'M' => [0x88, 0xD8, 0xA8, 0x88, 0x88, 0x88, 0x88, 0x00],
+        'N' => [0x88, 0xC8, 0xA8, 0x98, 0x88, 0x88, 0x88, 0x00],
+        'O' => [0x70, 0x88, 0x88, 0x88, 0x88, 0x88, 0x70, 0x00],
+        'P' => [0xF0, 0x88, 0x88, 0xF0, 0x80, 0x80, 0x80, 0x00],
+        'Q' => [0x70, 0x88, 0x88, 0x88, 0xA8, 0x90, 0x68, 0x00],
+        'R' => [0xF0, 0x88, 0x88, 0xF0, 0xA0, 0x90, 0x88, 0x00],
+        'S' => [0x78, 0x80, 0x80, 0x70, 0x08, 0x08, 0xF0, 0x00],
+        'T' => [0xF8, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x00],
+        'U' => [0x88, 0x88, 0x88, 0x88, 0x88, 0x88, 0x70, 0x00],
+        'V' => [0x88, 0x88, 0x88, 0x88, 0x88, 0x50, 0x20, 0x00],
+        'W' => [0x88, 0x88, 0x88, 0xA8, 0xA8, 0xD8, 0x88, 0x00],
+        'X' => [0x88, 0x50, 0x20, 0x20, 0x20, 0x50, 0x88, 0x00],
+        'Y' => [0x88, 0x50, 0x20, 0x20, 0x20, 0x20, 0x20, 0x00],
+        'Z' => [0xF8, 0x08, 0x10, 0x20, 0x40, 0x80, 0xF8, 0x00],
+        '.' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x60, 0x60, 0x00],
+        '-' => [0x00, 0x00, 0x00, 0xF8, 0x00, 0x00, 0x00, 0x00],
+        '!' => [0x20, 0x20, 0x20, 0x20, 0x20, 0x00, 0x20, 0x00],
+        ':' => [0x00, 0x60, 0x60, 0x00, 0x60, 0x60, 0x00, 0x00],
+        _ => [0x00; GLYPH_ROWS],
+    }
+}
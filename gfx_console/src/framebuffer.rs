@@ -0,0 +1,144 @@
+// The framebuffer blit engine shared by `kernel` and `zkernel` (pulled in
+// with `#[path]`, since there's no Cargo workspace yet to declare a real
+// path dependency on this crate) - edit this copy, not one under either
+// crate's `src/`. Renders glyphs from the embedded bitmap font directly
+// into the linear BGRA framebuffer the bootloader hands off, since a UEFI
+// GOP boot has no legacy VGA text buffer at 0xB8000 to fall back on.
+//
+// This only owns the pixel-level console: cursor position, glyph blitting,
+// scrolling, and the plain character stream in `write_str`/`write_char`.
+// Each crate wires that up to its own text-output glue (`TextDisplay` for
+// `zkernel`, `core::fmt::Write` for `kernel`) in its own `framebuffer.rs`.
+
+use crate::font::{self, GLYPH_ROWS, GLYPH_WIDTH};
+
+// Each authored glyph row is doubled to fill this many scanlines.
+const GLYPH_HEIGHT: usize = GLYPH_ROWS * 2;
+
+const FOREGROUND: u32 = 0x00FF_FFFF; // white
+const BACKGROUND: u32 = 0x0000_0000; // black
+const PANIC_FOREGROUND: u32 = 0x00FF_FFFF; // white
+const PANIC_BACKGROUND: u32 = 0x00FF_0000; // red
+
+pub struct FramebufferConsole {
+    base: *mut u8,
+    width: usize,
+    height: usize,
+    // Bytes per scanline.
+    stride: usize,
+    cursor_x: usize,
+    cursor_y: usize,
+    fg: u32,
+    bg: u32,
+}
+
+// Mark FramebufferConsole as safe to share between threads
+// (though we won't be using threads in our simple kernel)
+unsafe impl Sync for FramebufferConsole {}
+
+impl FramebufferConsole {
+    /// # Safety
+    /// `addr` must point to a valid, writable linear framebuffer at least
+    /// `stride * height` bytes long, as described by the bootloader's
+    /// `BootInfo`.
+    pub unsafe fn new(addr: u64, width: usize, height: usize, stride: usize) -> Self {
+        Self {
+            base: addr as *mut u8,
+            width,
+            height,
+            stride,
+            cursor_x: 0,
+            cursor_y: 0,
+            fg: FOREGROUND,
+            bg: BACKGROUND,
+        }
+    }
+
+    fn put_pixel(&mut self, x: usize, y: usize, color: u32) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        let offset = y * self.stride + x * 4;
+        unsafe { (self.base.add(offset) as *mut u32).write(color) };
+    }
+
+    fn draw_glyph(&mut self, c: char) {
+        for (row, bits) in font::glyph_rows(c).into_iter().enumerate() {
+            for col in 0..GLYPH_WIDTH {
+                let color = if bits & (0x80 >> col) != 0 {
+                    self.fg
+                } else {
+                    self.bg
+                };
+                let y = self.cursor_y + row * 2;
+                self.put_pixel(self.cursor_x + col, y, color);
+                self.put_pixel(self.cursor_x + col, y + 1, color);
+            }
+        }
+    }
+
+    // Fill every pixel with the console's current background color and
+    // reset the cursor.
+    pub fn clear(&mut self) {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                self.put_pixel(x, y, self.bg);
+            }
+        }
+        self.cursor_x = 0;
+        self.cursor_y = 0;
+    }
+
+    // Switch to the panic color scheme (white on red) and clear the screen
+    // with it. Callers write their diagnostic message afterward through
+    // whichever text-output glue they expose.
+    pub fn panic_mode(&mut self) {
+        self.fg = PANIC_FOREGROUND;
+        self.bg = PANIC_BACKGROUND;
+        self.clear();
+    }
+
+    fn advance_cursor(&mut self) {
+        self.cursor_x += GLYPH_WIDTH;
+        if self.cursor_x + GLYPH_WIDTH > self.width {
+            self.newline();
+        }
+    }
+
+    fn newline(&mut self) {
+        self.cursor_x = 0;
+        self.cursor_y += GLYPH_HEIGHT;
+        if self.cursor_y + GLYPH_HEIGHT > self.height {
+            self.scroll();
+        }
+    }
+
+    // Move every scanline below the top row up by one glyph's worth of
+    // rows and blank the row that scrolled in at the bottom.
+    fn scroll(&mut self) {
+        let shift = self.stride * GLYPH_HEIGHT;
+        let total = self.stride * self.height;
+        unsafe {
+            core::ptr::copy(self.base.add(shift), self.base, total - shift);
+            core::ptr::write_bytes(self.base.add(total - shift), 0, shift);
+        }
+        self.cursor_y -= GLYPH_HEIGHT;
+    }
+
+    pub fn write_char(&mut self, c: char) {
+        match c {
+            '\n' => self.newline(),
+            '\r' => self.cursor_x = 0,
+            _ => {
+                self.draw_glyph(c);
+                self.advance_cursor();
+            }
+        }
+    }
+
+    pub fn write_str(&mut self, s: &str) {
+        for c in s.chars() {
+            self.write_char(c);
+        }
+    }
+}
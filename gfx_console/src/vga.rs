@@ -0,0 +1,138 @@
+// The legacy VGA text-mode (0xB8000) console, shared by `kernel` and
+// `zkernel` (pulled in with `#[path]`, since there's no Cargo workspace yet
+// to declare a real path dependency on this crate) - edit this copy, not
+// one under either crate's `src/`. Used whenever the bootloader didn't hand
+// off a linear framebuffer, so there's no other display to fall back on.
+
+const VGA_WIDTH: usize = 80;
+const VGA_HEIGHT: usize = 25;
+const VGA_ATTR_NORMAL: u8 = 0x07; // gray on black
+const VGA_ATTR_PANIC: u8 = 0x4F; // white on red
+
+// A single VGA text-mode cell. Reads and writes go through
+// `core::ptr::write_volatile`/`read_volatile` so the optimizer can't elide or
+// reorder stores to this memory-mapped buffer.
+#[repr(transparent)]
+struct Cell(u16);
+
+impl Cell {
+    fn read(&self) -> u16 {
+        unsafe { core::ptr::read_volatile(&self.0) }
+    }
+
+    fn write(&mut self, value: u16) {
+        unsafe { core::ptr::write_volatile(&mut self.0, value) };
+    }
+}
+
+// A simple console that writes directly to VGA memory
+pub struct VgaConsole {
+    buffer: *mut [[Cell; VGA_WIDTH]; VGA_HEIGHT],
+    row: usize,
+    col: usize,
+    // Attribute byte (fg/bg color) new characters are written with.
+    attr: u8,
+}
+
+// Mark VgaConsole as safe to share between threads
+// (though we won't be using threads in our simple kernel)
+unsafe impl Sync for VgaConsole {}
+
+// Implementation of VGA console
+impl VgaConsole {
+    // Initialize a new VGA console
+    pub fn new() -> Self {
+        Self {
+            buffer: 0xB8000 as *mut [[Cell; VGA_WIDTH]; VGA_HEIGHT], // Standard VGA buffer address
+            row: 0,
+            col: 0,
+            attr: VGA_ATTR_NORMAL,
+        }
+    }
+
+    fn buffer(&mut self) -> &mut [[Cell; VGA_WIDTH]; VGA_HEIGHT] {
+        unsafe { &mut *self.buffer }
+    }
+
+    fn blank_cell(&self) -> u16 {
+        (self.attr as u16) << 8 | 0x20
+    }
+
+    // Fill the whole screen with blanks in the console's current attribute
+    // and reset the cursor.
+    pub fn clear(&mut self) {
+        let blank = self.blank_cell();
+        let buffer = self.buffer();
+        for row in buffer.iter_mut() {
+            for cell in row.iter_mut() {
+                cell.write(blank);
+            }
+        }
+        self.row = 0;
+        self.col = 0;
+    }
+
+    // Move to the next line
+    fn newline(&mut self) {
+        // Reset column to 0
+        self.col = 0;
+
+        // Increment row
+        let new_row = self.row + 1;
+
+        if new_row >= VGA_HEIGHT {
+            // Simple scrolling - move everything up one line, cell-by-cell
+            // through the volatile accessors so nothing gets reordered.
+            let blank = self.blank_cell();
+            let buffer = self.buffer();
+            for y in 1..VGA_HEIGHT {
+                for x in 0..VGA_WIDTH {
+                    let current = buffer[y][x].read();
+                    buffer[y - 1][x].write(current);
+                }
+            }
+            // Clear the last line
+            for x in 0..VGA_WIDTH {
+                buffer[VGA_HEIGHT - 1][x].write(blank);
+            }
+            self.row = VGA_HEIGHT - 1;
+        } else {
+            self.row = new_row;
+        }
+    }
+
+    // Switch to the panic color scheme (white on red) and clear the screen
+    // with it. Callers write their diagnostic message afterward through
+    // whichever text-output glue they expose.
+    pub fn panic_mode(&mut self) {
+        self.attr = VGA_ATTR_PANIC;
+        self.clear();
+    }
+
+    pub fn write_char(&mut self, c: char) {
+        match c {
+            '\n' => self.newline(),
+            '\r' => self.col = 0,
+            _ => {
+                // Write the character to the buffer
+                let char_with_attr = (self.attr as u16) << 8 | (c as u16);
+                let (row, col) = (self.row, self.col);
+                self.buffer()[row][col].write(char_with_attr);
+
+                // Advance cursor
+                let new_col = col + 1;
+                if new_col >= VGA_WIDTH {
+                    self.newline();
+                } else {
+                    self.col = new_col;
+                }
+            }
+        }
+    }
+
+    pub fn write_str(&mut self, s: &str) {
+        for c in s.chars() {
+            self.write_char(c);
+        }
+    }
+}
@@ -0,0 +1,43 @@
+// `kernel`'s `core::fmt::Write` glue over the shared `gfx_console`
+// framebuffer engine (see `main.rs`'s `mod engine` for how that's pulled
+// in). Everything pixel-level - cursor, glyph blitting, scrolling - lives
+// there; this crate has no allocator and no shared console trait (it's a
+// single small entry point, not the reactive-dispatcher kernel), so this
+// just adapts the engine to `core::fmt::Write`, which is enough to format
+// a `PanicInfo` without needing a heap.
+
+use core::fmt;
+
+use engine::FramebufferConsole as Engine;
+
+use crate::engine;
+
+pub struct FramebufferConsole(Engine);
+
+impl FramebufferConsole {
+    /// # Safety
+    /// `addr` must point to a valid, writable linear framebuffer at least
+    /// `stride * height` bytes long, as described by the bootloader's
+    /// `BootInfo`.
+    pub unsafe fn new(addr: u64, width: usize, height: usize, stride: usize) -> Self {
+        Self(unsafe { Engine::new(addr, width, height, stride) })
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    // Paint a full-screen diagnostic (white on red) and print `message`.
+    // Used only from the panic handler.
+    pub fn panic_screen(&mut self, message: fmt::Arguments) {
+        self.0.panic_mode();
+        let _ = fmt::Write::write_fmt(self, message);
+    }
+}
+
+impl fmt::Write for FramebufferConsole {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write_str(s);
+        Ok(())
+    }
+}
@@ -1,10 +1,61 @@
 #![no_std]
 #![no_main]
 
+use core::fmt::Write as _;
 use core::panic::PanicInfo;
+use core::sync::atomic::{AtomicBool, Ordering};
 
-// BGR color constants
-const COLOR_BLUE: u32 = 0x00FF0000;  // Blue in BGR format
+// The shared glyph table, framebuffer blit engine, and VGA text console,
+// pulled in from the sibling `gfx_console` crate with `#[path]` since
+// there's no Cargo workspace yet to declare it as a real path dependency.
+// `zkernel` pulls in the same files the same way - edit them there, not a
+// local copy.
+#[path = "../../gfx_console/src/font.rs"]
+mod font;
+#[path = "../../gfx_console/src/framebuffer.rs"]
+mod engine;
+#[path = "../../gfx_console/src/vga.rs"]
+mod vga_engine;
+mod framebuffer;
+mod vga;
+
+use framebuffer::FramebufferConsole;
+use vga::VgaConsole;
+
+// The primary display backend. A UEFI GOP boot has no legacy VGA text
+// buffer to write to, so the backend is picked at boot time based on
+// whether the bootloader handed off a framebuffer.
+enum Console {
+    Vga(VgaConsole),
+    Framebuffer(FramebufferConsole),
+}
+
+impl Console {
+    fn clear(&mut self) {
+        match self {
+            Console::Vga(console) => console.clear(),
+            Console::Framebuffer(console) => console.clear(),
+        }
+    }
+
+    // Paint a full-screen diagnostic (white on red) and print `message`.
+    // Used only from the panic handler.
+    fn panic_screen(&mut self, message: core::fmt::Arguments) {
+        match self {
+            Console::Vga(console) => console.panic_screen(message),
+            Console::Framebuffer(console) => console.panic_screen(message),
+        }
+    }
+}
+
+impl core::fmt::Write for Console {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        match self {
+            Console::Vga(console) => console.write_str(s),
+            Console::Framebuffer(console) => console.write_str(s),
+        }
+    }
+}
 
 #[repr(C)]
 pub struct BootInfo {
@@ -17,24 +68,36 @@ pub struct BootInfo {
     framebuffer_stride: usize,
 }
 
+// The console this entry point picked (`zkernel`'s `kernel_main` picks its
+// own the same way, in `EventDispatcher::new`). Set once in `_start` and
+// read by the panic handler; there's
+// no dispatcher or locking primitive here, just this one entry point and a
+// panic, so a raw global is enough.
+static mut CONSOLE: Option<Console> = None;
+
+// Set while `panic` is rendering a diagnostic, so a second panic triggered
+// by the rendering path itself (e.g. a broken framebuffer) doesn't recurse.
+static PANICKING: AtomicBool = AtomicBool::new(false);
+
 // Main kernel entry point
 #[no_mangle]
 pub extern "C" fn _start(boot_info: &'static BootInfo) -> ! {
-    // Fill the screen with blue
-    if boot_info.framebuffer_addr != 0 {
-        let fb = unsafe {
-            core::slice::from_raw_parts_mut(
-                boot_info.framebuffer_addr as *mut u32,
-                boot_info.framebuffer_width * boot_info.framebuffer_height
+    let mut console = if boot_info.framebuffer_addr != 0 {
+        Console::Framebuffer(unsafe {
+            FramebufferConsole::new(
+                boot_info.framebuffer_addr,
+                boot_info.framebuffer_width,
+                boot_info.framebuffer_height,
+                boot_info.framebuffer_stride,
             )
-        };
-        
-        // Simple approach - fill entire screen with blue
-        for i in 0..fb.len() {
-            fb[i] = COLOR_BLUE;
-        }
-    }
-    
+        })
+    } else {
+        Console::Vga(VgaConsole::new())
+    };
+    console.clear();
+    let _ = console.write_str("GRAPEOS BOOT OK\n\r");
+    unsafe { *core::ptr::addr_of_mut!(CONSOLE) = Some(console) };
+
     // Hang forever
     loop {
         unsafe { core::arch::asm!("hlt"); }
@@ -43,8 +106,13 @@ pub extern "C" fn _start(boot_info: &'static BootInfo) -> ! {
 
 // Panic handler
 #[panic_handler]
-fn panic(_info: &PanicInfo) -> ! {
+fn panic(info: &PanicInfo) -> ! {
+    if !PANICKING.swap(true, Ordering::SeqCst) {
+        if let Some(console) = unsafe { (*core::ptr::addr_of_mut!(CONSOLE)).as_mut() } {
+            console.panic_screen(format_args!("{}", info));
+        }
+    }
     loop {
         unsafe { core::arch::asm!("hlt"); }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,34 @@
+// `kernel`'s `core::fmt::Write` glue over the shared `gfx_console` VGA
+// engine (see `main.rs`'s `mod vga_engine` for how that's pulled in).
+
+use core::fmt;
+
+use vga_engine::VgaConsole as Engine;
+
+use crate::vga_engine;
+
+pub struct VgaConsole(Engine);
+
+impl VgaConsole {
+    pub fn new() -> Self {
+        Self(Engine::new())
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    // Paint a full-screen diagnostic (white on red) and print `message`.
+    // Used only from the panic handler.
+    pub fn panic_screen(&mut self, message: fmt::Arguments) {
+        self.0.panic_mode();
+        let _ = fmt::Write::write_fmt(self, message);
+    }
+}
+
+impl fmt::Write for VgaConsole {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write_str(s);
+        Ok(())
+    }
+}
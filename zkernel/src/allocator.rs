@@ -0,0 +1,129 @@
+// A bump allocator registered as the `#[global_allocator]`, so `alloc`'s
+// `Vec`/`Box` work without a general-purpose heap implementation. It never
+// reclaims individual allocations - acceptable for now since nothing in the
+// kernel frees memory yet - but it does get its backing region from the
+// UEFI memory map the bootloader already handed us, instead of just
+// reserving a fixed static array and ignoring that map entirely.
+
+use core::alloc::{GlobalAlloc, Layout};
+use core::cell::UnsafeCell;
+
+use crate::boot_info::BootInfo;
+
+const EFI_CONVENTIONAL_MEMORY: u32 = 7;
+const PAGE_SIZE: u64 = 4096;
+
+// Used if the bootloader didn't hand us a usable memory map (e.g. a BIOS
+// boot path that never populates one).
+const FALLBACK_HEAP_SIZE: usize = 1 << 20; // 1 MiB
+static mut FALLBACK_HEAP: [u8; FALLBACK_HEAP_SIZE] = [0; FALLBACK_HEAP_SIZE];
+
+// A UEFI `EFI_MEMORY_DESCRIPTOR`, as produced by `GetMemoryMap`. Layout is
+// fixed by the UEFI spec; the map may stride by a larger `entry_size` than
+// `size_of::<EfiMemoryDescriptor>()` to leave room for future fields, so
+// callers must always advance by `memory_map_entry_size`, never `size_of`.
+#[repr(C)]
+struct EfiMemoryDescriptor {
+    ty: u32,
+    padding: u32,
+    physical_start: u64,
+    virtual_start: u64,
+    number_of_pages: u64,
+    attribute: u64,
+}
+
+// Find the largest free (conventional) region described by the memory map.
+fn largest_conventional_region(boot_info: &BootInfo) -> Option<(usize, usize)> {
+    if boot_info.memory_map_addr == 0 || boot_info.memory_map_entry_size == 0 {
+        return None;
+    }
+
+    let entries = boot_info.memory_map_size / boot_info.memory_map_entry_size;
+    let base = boot_info.memory_map_addr as *const u8;
+
+    let mut best: Option<(usize, usize)> = None;
+    for i in 0..entries {
+        let entry = unsafe {
+            let ptr = base.add(i * boot_info.memory_map_entry_size) as *const EfiMemoryDescriptor;
+            core::ptr::read_unaligned(ptr)
+        };
+        if entry.ty != EFI_CONVENTIONAL_MEMORY {
+            continue;
+        }
+        let size = (entry.number_of_pages * PAGE_SIZE) as usize;
+        if best.is_none_or(|(_, best_size)| size > best_size) {
+            best = Some((entry.physical_start as usize, size));
+        }
+    }
+    best
+}
+
+struct BumpState {
+    start: usize,
+    end: usize,
+    next: usize,
+}
+
+pub struct BumpAllocator {
+    state: UnsafeCell<BumpState>,
+}
+
+// Mark BumpAllocator as safe to share between threads
+// (though we won't be using threads in our simple kernel)
+unsafe impl Sync for BumpAllocator {}
+
+impl BumpAllocator {
+    const fn new() -> Self {
+        Self {
+            state: UnsafeCell::new(BumpState {
+                start: 0,
+                end: 0,
+                next: 0,
+            }),
+        }
+    }
+
+    unsafe fn reset(&self, start: usize, size: usize) {
+        let state = unsafe { &mut *self.state.get() };
+        state.start = start;
+        state.end = start + size;
+        state.next = start;
+    }
+}
+
+fn align_up(addr: usize, align: usize) -> usize {
+    (addr + align - 1) & !(align - 1)
+}
+
+unsafe impl GlobalAlloc for BumpAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let state = unsafe { &mut *self.state.get() };
+
+        let aligned = align_up(state.next, layout.align());
+        let Some(new_next) = aligned.checked_add(layout.size()) else {
+            return core::ptr::null_mut();
+        };
+        if new_next > state.end {
+            return core::ptr::null_mut();
+        }
+
+        state.next = new_next;
+        aligned as *mut u8
+    }
+
+    unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {
+        // A pure bump allocator never reclaims individual allocations.
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: BumpAllocator = BumpAllocator::new();
+
+/// Point the global allocator at a region of memory to hand out: the
+/// largest conventional region in the bootloader's memory map if one was
+/// provided, otherwise a fallback static region.
+pub fn init(boot_info: &BootInfo) {
+    let (start, size) = largest_conventional_region(boot_info)
+        .unwrap_or_else(|| (core::ptr::addr_of_mut!(FALLBACK_HEAP) as usize, FALLBACK_HEAP_SIZE));
+    unsafe { ALLOCATOR.reset(start, size) };
+}
@@ -0,0 +1,14 @@
+// Mirrors `uefi_bootloader::common::BootInfo` field-for-field. The
+// bootloader and kernel are built and linked separately, so rather than
+// share a crate they agree on this `repr(C)` layout as their ABI.
+
+#[repr(C)]
+pub struct BootInfo {
+    pub memory_map_addr: u64,
+    pub memory_map_size: usize,
+    pub memory_map_entry_size: usize,
+    pub framebuffer_addr: u64,
+    pub framebuffer_width: usize,
+    pub framebuffer_height: usize,
+    pub framebuffer_stride: usize,
+}
@@ -0,0 +1,104 @@
+// A bounded priority queue for `Event`s, backed by a binary max-heap stored
+// in a fixed-size array. The capacity is chosen up front and a full queue
+// drops its least important pending event rather than growing.
+
+use crate::Event;
+
+pub const CAPACITY: usize = 16;
+
+pub struct EventQueue {
+    items: [Option<Event>; CAPACITY],
+    len: usize,
+}
+
+impl EventQueue {
+    pub const fn new() -> Self {
+        Self {
+            items: [None; CAPACITY],
+            len: 0,
+        }
+    }
+
+    // True if `a` should come out of the queue before `b`: higher priority
+    // wins, ties broken by whichever arrived first (FIFO).
+    fn better(a: Event, b: Event) -> bool {
+        a.priority > b.priority || (a.priority == b.priority && a.timestamp < b.timestamp)
+    }
+
+    fn get(&self, index: usize) -> Event {
+        self.items[index].unwrap()
+    }
+
+    // Push `event` onto the queue. If the queue is already at capacity, the
+    // least important entry currently queued is evicted to make room -
+    // unless `event` itself is the least important of the two, in which
+    // case it is simply dropped.
+    pub fn push(&mut self, event: Event) {
+        if self.len < CAPACITY {
+            self.items[self.len] = Some(event);
+            self.len += 1;
+            self.sift_up(self.len - 1);
+            return;
+        }
+
+        let mut worst = 0;
+        for i in 1..self.len {
+            if Self::better(self.get(worst), self.get(i)) {
+                worst = i;
+            }
+        }
+
+        if Self::better(event, self.get(worst)) {
+            self.items[worst] = Some(event);
+            self.sift_up(worst);
+            self.sift_down(worst);
+        }
+    }
+
+    // Remove and return the highest-priority event, if any.
+    pub fn pop(&mut self) -> Option<Event> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let top = self.items[0].take();
+        self.len -= 1;
+        if self.len > 0 {
+            self.items[0] = self.items[self.len].take();
+            self.sift_down(0);
+        }
+        top
+    }
+
+    fn sift_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            if Self::better(self.get(index), self.get(parent)) {
+                self.items.swap(index, parent);
+                index = parent;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn sift_down(&mut self, mut index: usize) {
+        loop {
+            let left = index * 2 + 1;
+            let right = index * 2 + 2;
+            let mut best = index;
+
+            if left < self.len && Self::better(self.get(left), self.get(best)) {
+                best = left;
+            }
+            if right < self.len && Self::better(self.get(right), self.get(best)) {
+                best = right;
+            }
+            if best == index {
+                break;
+            }
+            self.items.swap(index, best);
+            index = best;
+        }
+    }
+}
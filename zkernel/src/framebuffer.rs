@@ -0,0 +1,42 @@
+// `zkernel`'s `TextDisplay` glue over the shared `gfx_console` framebuffer
+// engine (see `main.rs`'s `mod engine` for how that's pulled in). Everything
+// pixel-level - cursor, glyph blitting, scrolling - lives there; this file
+// only adapts it to this crate's console trait and panic entry point.
+
+use engine::FramebufferConsole as Engine;
+
+use crate::engine;
+use crate::TextDisplay;
+
+pub struct FramebufferConsole(Engine);
+
+impl FramebufferConsole {
+    /// # Safety
+    /// `addr` must point to a valid, writable linear framebuffer at least
+    /// `stride * height` bytes long, as described by the bootloader's
+    /// `BootInfo`.
+    pub unsafe fn new(addr: u64, width: usize, height: usize, stride: usize) -> Self {
+        Self(unsafe { Engine::new(addr, width, height, stride) })
+    }
+
+    // Paint a full-screen diagnostic (white on red) and print `message`.
+    // Used only from the panic handler.
+    pub fn panic_screen(&mut self, message: &str) {
+        self.0.panic_mode();
+        self.0.write_str(message);
+    }
+}
+
+impl TextDisplay for FramebufferConsole {
+    fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    fn write_char(&mut self, c: char) {
+        self.0.write_char(c);
+    }
+
+    fn write_str(&mut self, s: &str) {
+        self.0.write_str(s);
+    }
+}
@@ -0,0 +1,97 @@
+// A minimal Interrupt Descriptor Table so the timer and keyboard IRQs can be
+// handled directly instead of being faked from `kernel_main`.
+
+use crate::pic;
+
+/// Vector the timer (IRQ0) is wired to after `pic::remap`.
+pub const TIMER_VECTOR: u8 = pic::PIC1_OFFSET;
+/// Vector the keyboard (IRQ1) is wired to after `pic::remap`.
+pub const KEYBOARD_VECTOR: u8 = pic::PIC1_OFFSET + 1;
+
+// Kernel code segment selector set up by the bootloader's GDT.
+const KERNEL_CODE_SELECTOR: u16 = 0x08;
+
+const ENTRY_COUNT: usize = 256;
+
+/// The frame the CPU pushes before invoking an interrupt handler, described
+/// so `extern "x86-interrupt"` handlers can declare it as their argument.
+#[repr(C)]
+pub struct InterruptStackFrame {
+    pub instruction_pointer: u64,
+    pub code_segment: u64,
+    pub cpu_flags: u64,
+    pub stack_pointer: u64,
+    pub stack_segment: u64,
+}
+
+#[derive(Clone, Copy)]
+#[repr(C, packed)]
+struct IdtEntry {
+    offset_low: u16,
+    selector: u16,
+    ist: u8,
+    type_attr: u8,
+    offset_mid: u16,
+    offset_high: u32,
+    reserved: u32,
+}
+
+impl IdtEntry {
+    const fn missing() -> Self {
+        Self {
+            offset_low: 0,
+            selector: 0,
+            ist: 0,
+            type_attr: 0,
+            offset_mid: 0,
+            offset_high: 0,
+            reserved: 0,
+        }
+    }
+
+    fn set_handler(&mut self, handler: u64) {
+        self.offset_low = handler as u16;
+        self.offset_mid = (handler >> 16) as u16;
+        self.offset_high = (handler >> 32) as u32;
+        self.selector = KERNEL_CODE_SELECTOR;
+        self.ist = 0;
+        self.type_attr = 0x8E; // present, ring 0, 64-bit interrupt gate
+    }
+}
+
+#[repr(C, packed)]
+struct IdtPointer {
+    limit: u16,
+    base: u64,
+}
+
+static mut IDT: [IdtEntry; ENTRY_COUNT] = [IdtEntry::missing(); ENTRY_COUNT];
+
+extern "x86-interrupt" fn timer_handler(_frame: InterruptStackFrame) {
+    crate::on_timer_interrupt();
+    unsafe { pic::send_eoi(0) };
+}
+
+extern "x86-interrupt" fn keyboard_handler(_frame: InterruptStackFrame) {
+    crate::on_keyboard_interrupt();
+    unsafe { pic::send_eoi(1) };
+}
+
+/// Install the timer and keyboard handlers and load the IDT with `lidt`.
+pub fn init() {
+    unsafe {
+        let idt = &mut *core::ptr::addr_of_mut!(IDT);
+        idt[TIMER_VECTOR as usize].set_handler(timer_handler as *const () as u64);
+        idt[KEYBOARD_VECTOR as usize].set_handler(keyboard_handler as *const () as u64);
+
+        let pointer = IdtPointer {
+            limit: (size_of_idt() - 1) as u16,
+            base: core::ptr::addr_of!(IDT) as u64,
+        };
+        core::arch::asm!("lidt [{}]", in(reg) &pointer, options(readonly, nostack, preserves_flags));
+    }
+}
+
+fn size_of_idt() -> usize {
+    core::mem::size_of::<[IdtEntry; ENTRY_COUNT]>()
+}
@@ -0,0 +1,29 @@
+// Helpers for protecting state shared between `kernel_main`'s idle loop and
+// the timer/keyboard ISRs. Interrupt gates (see `idt.rs`) already clear IF on
+// entry and restore it from the saved flags on `iret`, so an ISR body never
+// races itself - but the idle loop runs with IF set, and an ISR can still
+// preempt it mid-mutation of the same `EventQueue`. Wrapping a critical
+// section in `without_interrupts` makes it atomic with respect to both.
+
+use core::arch::asm;
+
+const INTERRUPT_FLAG: u64 = 1 << 9;
+
+/// Run `f` with interrupts disabled, restoring the previous interrupt flag
+/// (rather than unconditionally re-enabling) so nesting - e.g. an ISR that
+/// calls this while IF is already clear - doesn't turn interrupts back on
+/// early.
+pub fn without_interrupts<R>(f: impl FnOnce() -> R) -> R {
+    let flags: u64;
+    unsafe {
+        asm!("pushfq; pop {}", out(reg) flags, options(nomem, preserves_flags));
+        asm!("cli", options(nomem, nostack, preserves_flags));
+    }
+
+    let result = f();
+
+    if flags & INTERRUPT_FLAG != 0 {
+        unsafe { asm!("sti", options(nomem, nostack, preserves_flags)) };
+    }
+    result
+}
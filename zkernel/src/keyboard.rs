@@ -0,0 +1,166 @@
+// Decodes PS/2 Scancode Set 1 bytes (read from the keyboard's data port,
+// 0x60) into characters. Tracks just enough state across interrupts to
+// handle the 0xE0 extended-key prefix, make/break codes, and the shift,
+// ctrl, and caps lock modifiers.
+
+use crate::port::inb;
+
+const DATA_PORT: u16 = 0x60;
+
+const LEFT_SHIFT: u8 = 0x2A;
+const RIGHT_SHIFT: u8 = 0x36;
+const CTRL: u8 = 0x1D;
+const CAPS_LOCK: u8 = 0x3A;
+const BREAK_BIT: u8 = 0x80;
+
+/// A single decoded keystroke, carrying both the resolved character (if the
+/// key maps to one) and the modifier state it was decoded under.
+#[derive(Debug, Clone, Copy)]
+pub struct Keystroke {
+    pub character: Option<char>,
+    pub shift: bool,
+    pub ctrl: bool,
+    pub caps_lock: bool,
+}
+
+struct Keyboard {
+    shift: bool,
+    ctrl: bool,
+    caps_lock: bool,
+    extended: bool,
+}
+
+impl Keyboard {
+    const fn new() -> Self {
+        Self {
+            shift: false,
+            ctrl: false,
+            caps_lock: false,
+            extended: false,
+        }
+    }
+
+    // Feed one scancode byte through the state machine, returning a
+    // decoded keystroke for make codes of ordinary (non-modifier,
+    // non-extended) keys; its `character` is `None` if the key has no
+    // entry in the US-QWERTY table.
+    fn handle_scancode(&mut self, code: u8) -> Option<Keystroke> {
+        if code == 0xE0 {
+            self.extended = true;
+            return None;
+        }
+        let extended = core::mem::replace(&mut self.extended, false);
+
+        let is_break = code & BREAK_BIT != 0;
+        let make_code = code & !BREAK_BIT;
+
+        match make_code {
+            LEFT_SHIFT | RIGHT_SHIFT if !extended => {
+                self.shift = !is_break;
+                return None;
+            }
+            CTRL => {
+                self.ctrl = !is_break;
+                return None;
+            }
+            CAPS_LOCK if !extended => {
+                if !is_break {
+                    self.caps_lock = !self.caps_lock;
+                }
+                return None;
+            }
+            _ => {}
+        }
+
+        // Only make codes of ordinary (non-extended) keys map to characters.
+        if is_break || extended {
+            return None;
+        }
+
+        Some(Keystroke {
+            character: self.decode_char(make_code),
+            shift: self.shift,
+            ctrl: self.ctrl,
+            caps_lock: self.caps_lock,
+        })
+    }
+
+    fn decode_char(&self, make_code: u8) -> Option<char> {
+        let (unshifted, shifted) = us_qwerty_row(make_code)?;
+
+        let use_shifted = if unshifted.is_ascii_alphabetic() {
+            // Caps lock only affects letters, and stacks with shift.
+            self.shift ^ self.caps_lock
+        } else {
+            self.shift
+        };
+
+        Some(if use_shifted { shifted } else { unshifted })
+    }
+}
+
+// US-QWERTY (unshifted, shifted) pairs for Scancode Set 1 make codes.
+fn us_qwerty_row(make_code: u8) -> Option<(char, char)> {
+    Some(match make_code {
+        0x02 => ('1', '!'),
+        0x03 => ('2', '@'),
+        0x04 => ('3', '#'),
+        0x05 => ('4', '$'),
+        0x06 => ('5', '%'),
+        0x07 => ('6', '^'),
+        0x08 => ('7', '&'),
+        0x09 => ('8', '*'),
+        0x0A => ('9', '('),
+        0x0B => ('0', ')'),
+        0x0C => ('-', '_'),
+        0x0D => ('=', '+'),
+        0x0F => ('\t', '\t'),
+        0x10 => ('q', 'Q'),
+        0x11 => ('w', 'W'),
+        0x12 => ('e', 'E'),
+        0x13 => ('r', 'R'),
+        0x14 => ('t', 'T'),
+        0x15 => ('y', 'Y'),
+        0x16 => ('u', 'U'),
+        0x17 => ('i', 'I'),
+        0x18 => ('o', 'O'),
+        0x19 => ('p', 'P'),
+        0x1A => ('[', '{'),
+        0x1B => (']', '}'),
+        0x1C => ('\n', '\n'),
+        0x1E => ('a', 'A'),
+        0x1F => ('s', 'S'),
+        0x20 => ('d', 'D'),
+        0x21 => ('f', 'F'),
+        0x22 => ('g', 'G'),
+        0x23 => ('h', 'H'),
+        0x24 => ('j', 'J'),
+        0x25 => ('k', 'K'),
+        0x26 => ('l', 'L'),
+        0x27 => (';', ':'),
+        0x28 => ('\'', '"'),
+        0x29 => ('`', '~'),
+        0x2B => ('\\', '|'),
+        0x2C => ('z', 'Z'),
+        0x2D => ('x', 'X'),
+        0x2E => ('c', 'C'),
+        0x2F => ('v', 'V'),
+        0x30 => ('b', 'B'),
+        0x31 => ('n', 'N'),
+        0x32 => ('m', 'M'),
+        0x33 => (',', '<'),
+        0x34 => ('.', '>'),
+        0x35 => ('/', '?'),
+        0x39 => (' ', ' '),
+        _ => return None,
+    })
+}
+
+static mut KEYBOARD: Keyboard = Keyboard::new();
+
+/// Read the pending scancode off the data port and run it through the
+/// decoder. Call once per keyboard (IRQ1) interrupt.
+pub fn poll() -> Option<Keystroke> {
+    let code = unsafe { inb(DATA_PORT) };
+    unsafe { (*core::ptr::addr_of_mut!(KEYBOARD)).handle_scancode(code) }
+}
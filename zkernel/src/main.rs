@@ -1,10 +1,44 @@
 #![no_std]
 #![no_main]
 #![feature(naked_functions)]
+#![feature(abi_x86_interrupt)]
+#![feature(alloc_error_handler)]
 
+extern crate alloc;
+
+mod allocator;
+mod boot_info;
+// The shared glyph table, framebuffer blit engine, and VGA text console,
+// pulled in from the sibling `gfx_console` crate with `#[path]` since
+// there's no Cargo workspace yet to declare it as a real path dependency.
+// `kernel` pulls in the same files the same way - edit them there, not a
+// local copy.
+#[path = "../../gfx_console/src/font.rs"]
+mod font;
+#[path = "../../gfx_console/src/framebuffer.rs"]
+mod engine;
+#[path = "../../gfx_console/src/vga.rs"]
+mod vga_engine;
+mod event_queue;
+mod framebuffer;
+mod idt;
+mod interrupts;
+mod keyboard;
+mod pic;
+mod port;
+mod serial;
+mod vga;
+
+use core::alloc::Layout;
 use core::panic::PanicInfo;
-use core::sync::atomic::{AtomicBool, Ordering};
-use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use boot_info::BootInfo;
+use event_queue::EventQueue;
+use framebuffer::FramebufferConsole;
+use keyboard::Keystroke;
+use serial::SerialConsole;
+use vga::VgaConsole;
 
 // Kernel header structure - must match the bootloader's expectation
 #[repr(C)]
@@ -23,6 +57,15 @@ const KERNEL_MAGIC: u64 = 0x4752415045_4F53_00;
 // Global state for our simple event system
 static SYSTEM_RUNNING: AtomicBool = AtomicBool::new(false);
 
+// There's no RTC/TSC reader yet, so this just counts events as they're
+// created. That's all the priority queue's FIFO tiebreaker needs: a value
+// that strictly increases in arrival order.
+static NEXT_TIMESTAMP: AtomicU64 = AtomicU64::new(0);
+
+fn next_timestamp() -> u64 {
+    NEXT_TIMESTAMP.fetch_add(1, Ordering::Relaxed)
+}
+
 // Event types for our reactive OS
 #[derive(Debug, Clone, Copy)]
 #[repr(u8)]
@@ -40,6 +83,9 @@ struct Event {
     event_type: EventType,
     priority: u8,  // Higher number = higher priority
     timestamp: u64,
+    // Populated for `KeyPress` events decoded from a real scancode; `None`
+    // for synthetic events that have no associated keystroke.
+    key: Option<Keystroke>,
 }
 
 // Create a static kernel header at the start of the kernel
@@ -60,122 +106,122 @@ trait TextDisplay {
     fn write_char(&mut self, c: char);
 }
 
-// A simple console that writes directly to VGA memory
-struct VgaConsole {
-    buffer: *mut u16,
-    row: UnsafeCell<usize>,
-    col: UnsafeCell<usize>,
-}
-
-// Mark VgaConsole as safe to share between threads
-// (though we won't be using threads in our simple kernel)
-unsafe impl Sync for VgaConsole {}
 
-// Implementation of VGA console
-impl VgaConsole {
-    // Initialize a new VGA console
-    fn new() -> Self {
-        Self {
-            buffer: 0xB8000 as *mut u16, // Standard VGA buffer address
-            row: UnsafeCell::new(0),
-            col: UnsafeCell::new(0),
-        }
-    }
-    
-    // Move to the next line
-    fn newline(&mut self) {
-        // Get current row and col
-        let row = unsafe { *self.row.get() };
-        
-        // Reset column to 0
-        unsafe { *self.col.get() = 0 };
-        
-        // Increment row
-        let new_row = row + 1;
-        
-        if new_row >= 25 {
-            // Simple scrolling - move everything up one line
-            for y in 1..25 {
-                for x in 0..80 {
-                    unsafe {
-                        let current = *self.buffer.add(y * 80 + x);
-                        *self.buffer.add((y - 1) * 80 + x) = current;
-                    }
-                }
-            }
-            // Clear the last line
-            for x in 0..80 {
-                unsafe { 
-                    *self.buffer.add(24 * 80 + x) = 0x0720; // Space with gray on black
-                }
-            }
-            unsafe { *self.row.get() = 24 };
-        } else {
-            unsafe { *self.row.get() = new_row };
-        }
-    }
+// The primary display backend. A UEFI GOP boot has no legacy VGA text
+// buffer to write to, so the backend is picked at boot time based on
+// whether the bootloader handed off a framebuffer.
+enum Console {
+    Vga(VgaConsole),
+    Framebuffer(FramebufferConsole),
 }
 
-// Implement TextDisplay for VgaConsole
-impl TextDisplay for VgaConsole {
+impl TextDisplay for Console {
     fn clear(&mut self) {
-        for i in 0..(80 * 25) {
-            unsafe { 
-                *self.buffer.add(i) = 0x0720; // Space with gray on black
-            }
-        }
-        
-        // Reset cursor position
-        unsafe {
-            *self.row.get() = 0;
-            *self.col.get() = 0;
+        match self {
+            Console::Vga(console) => console.clear(),
+            Console::Framebuffer(console) => console.clear(),
         }
     }
-    
+
     fn write_char(&mut self, c: char) {
-        // Get current row and col
-        let row = unsafe { *self.row.get() };
-        let col = unsafe { *self.col.get() };
-        
-        match c {
-            '\n' => self.newline(),
-            '\r' => unsafe { *self.col.get() = 0 },
-            _ => {
-                // Write the character to the buffer
-                let char_with_attr = 0x0700 | (c as u16); // Gray on black
-                unsafe { 
-                    *self.buffer.add(row * 80 + col) = char_with_attr;
-                }
-                
-                // Advance cursor
-                let new_col = col + 1;
-                if new_col >= 80 {
-                    self.newline();
-                } else {
-                    unsafe { *self.col.get() = new_col };
-                }
-            }
+        match self {
+            Console::Vga(console) => console.write_char(c),
+            Console::Framebuffer(console) => console.write_char(c),
         }
     }
-    
+
     fn write_str(&mut self, s: &str) {
-        for c in s.chars() {
-            self.write_char(c);
+        match self {
+            Console::Vga(console) => console.write_str(s),
+            Console::Framebuffer(console) => console.write_str(s),
+        }
+    }
+}
+
+impl Console {
+    // Paint a full-screen diagnostic (white on red) and print `message`.
+    // Used only from the panic handler.
+    fn panic_screen(&mut self, message: &str) {
+        match self {
+            Console::Vga(console) => console.panic_screen(message),
+            Console::Framebuffer(console) => console.panic_screen(message),
         }
     }
 }
 
 // Event dispatcher - the heart of our reactive system
 struct EventDispatcher {
-    console: VgaConsole,
+    console: Console,
+    // Secondary backend so boot logs are visible on a headless QEMU instance
+    // (and capturable in CI) even when nothing is watching the VGA buffer.
+    serial: Option<SerialConsole>,
+    // Events queued by interrupt handlers, drained by priority in `run`.
+    queue: EventQueue,
 }
 
 impl EventDispatcher {
-    fn new() -> Self {
-        let console = VgaConsole::new();
-        Self { console }
+    fn new(boot_info: &BootInfo) -> Self {
+        let console = if boot_info.framebuffer_addr != 0 {
+            Console::Framebuffer(unsafe {
+                FramebufferConsole::new(
+                    boot_info.framebuffer_addr,
+                    boot_info.framebuffer_width,
+                    boot_info.framebuffer_height,
+                    boot_info.framebuffer_stride,
+                )
+            })
+        } else {
+            Console::Vga(VgaConsole::new())
+        };
+        let serial = Some(SerialConsole::new());
+        Self {
+            console,
+            serial,
+            queue: EventQueue::new(),
+        }
     }
-    
+
+    // Queue an event for `run` to dispatch, highest priority first. Called
+    // from both the idle loop and interrupt handlers, so the heap mutation
+    // is wrapped to be atomic with respect to either.
+    fn enqueue(&mut self, event: Event) {
+        let queue = &mut self.queue;
+        interrupts::without_interrupts(|| queue.push(event));
+    }
+
+    // Dispatch every queued event, highest priority first, until the queue
+    // is empty or a `SystemShutdown` event has been processed. Only the pop
+    // itself needs to be atomic with respect to an ISR's `enqueue`; dispatch
+    // can safely run with interrupts enabled.
+    fn run(&mut self) {
+        loop {
+            let queue = &mut self.queue;
+            let Some(event) = interrupts::without_interrupts(|| queue.pop()) else {
+                break;
+            };
+            let shutdown = matches!(event.event_type, EventType::SystemShutdown);
+            self.dispatch_event(event);
+            if shutdown {
+                break;
+            }
+        }
+    }
+
+    // Write to every active console backend.
+    fn write_str(&mut self, s: &str) {
+        self.console.write_str(s);
+        if let Some(serial) = &mut self.serial {
+            serial.write_str(s);
+        }
+    }
+
+    fn write_char(&mut self, c: char) {
+        self.console.write_char(c);
+        if let Some(serial) = &mut self.serial {
+            serial.write_char(c);
+        }
+    }
+
     // Dispatch an event to the appropriate handler
     fn dispatch_event(&mut self, event: Event) {
         // In a real system, we would have a queue and priority-based scheduling
@@ -186,27 +232,30 @@ impl EventDispatcher {
             EventType::SystemShutdown => self.handle_system_shutdown(event),
         }
     }
-    
+
     // Event handlers
     fn handle_system_init(&mut self, _event: Event) {
         self.console.clear();
-        self.console.write_str("GrapeOS Kernel Initialized\n\r");
-        self.console.write_str("---------------------------\n\r");
-        self.console.write_str("Welcome to the Reactive Operating System!\n\r");
-        
+        self.write_str("GrapeOS Kernel Initialized\n\r");
+        self.write_str("---------------------------\n\r");
+        self.write_str("Welcome to the Reactive Operating System!\n\r");
+
         SYSTEM_RUNNING.store(true, Ordering::SeqCst);
     }
-    
-    fn handle_key_press(&mut self, _event: Event) {
-        self.console.write_str("Key press detected\n\r");
+
+    fn handle_key_press(&mut self, event: Event) {
+        match event.key.and_then(|key| key.character) {
+            Some(c) => self.write_char(c),
+            None => self.write_str("Key press detected\n\r"),
+        }
     }
-    
+
     fn handle_timer(&mut self, _event: Event) {
-        self.console.write_str(".");
+        self.write_str(".");
     }
-    
+
     fn handle_system_shutdown(&mut self, _event: Event) {
-        self.console.write_str("\n\rShutting down...\n\r");
+        self.write_str("\n\rShutting down...\n\r");
         SYSTEM_RUNNING.store(false, Ordering::SeqCst);
     }
     
@@ -215,56 +264,113 @@ impl EventDispatcher {
         Event {
             event_type,
             priority,
-            timestamp: 0, // For now, just use a dummy timestamp
+            timestamp: next_timestamp(),
+            key: None,
+        }
+    }
+
+    // Create a `KeyPress` event carrying a decoded keystroke.
+    fn create_key_event(&self, keystroke: Keystroke, priority: u8) -> Event {
+        Event {
+            event_type: EventType::KeyPress,
+            priority,
+            timestamp: next_timestamp(),
+            key: Some(keystroke),
         }
     }
 }
 
+// The dispatcher is created once in `kernel_main` and then lives for the
+// rest of the kernel's life, so interrupt handlers need a way to reach it
+// too. There's no locking primitive yet, so a raw global is the simplest
+// thing that works: interrupts are off until we're done setting it up, and
+// afterwards the idle loop and the ISRs share it only through the queue
+// operations that `enqueue`/`run` wrap in `without_interrupts`.
+static mut DISPATCHER: Option<EventDispatcher> = None;
+
+// Single accessor for the global so every caller goes through one raw
+// pointer instead of each taking its own `&mut` to the static.
+fn dispatcher() -> &'static mut EventDispatcher {
+    unsafe { (*core::ptr::addr_of_mut!(DISPATCHER)).as_mut().unwrap() }
+}
+
+// Called from the IRQ0 (timer) handler.
+fn on_timer_interrupt() {
+    let dispatcher = dispatcher();
+    let event = dispatcher.create_event(EventType::Timer, 5);
+    dispatcher.enqueue(event);
+}
+
+// Called from the IRQ1 (keyboard) handler.
+fn on_keyboard_interrupt() {
+    let Some(keystroke) = keyboard::poll() else {
+        return;
+    };
+    let dispatcher = dispatcher();
+    let event = dispatcher.create_key_event(keystroke, 8);
+    dispatcher.enqueue(event);
+}
+
 // Kernel main function - called by the bootloader
 #[unsafe(no_mangle)]
-pub extern "C" fn kernel_main() -> ! {
+pub extern "C" fn kernel_main(boot_info: &'static BootInfo) -> ! {
     // Initialize the core system
-    let mut dispatcher = EventDispatcher::new();
-    
+    allocator::init(boot_info);
+    unsafe { *core::ptr::addr_of_mut!(DISPATCHER) = Some(EventDispatcher::new(boot_info)) };
+    let dispatcher = dispatcher();
+
     // Send system initialization event
     let init_event = dispatcher.create_event(EventType::SystemInit, 10);
     dispatcher.dispatch_event(init_event);
-    
-    // Main event loop - in a real system, this would be driven by hardware events
-    for _i in 0..10 {
-        // Create a timer event every second
-        let timer_event = dispatcher.create_event(EventType::Timer, 5);
-        dispatcher.dispatch_event(timer_event);
-        
-        // Simulate a delay - in a real system, this would be handled by the CPU's timer
-        for _ in 0..5000000 {
-            // Simple delay loop
-            core::hint::spin_loop();
-        }
+
+    // Take over interrupt handling: install the IDT, remap the PIC off the
+    // CPU exception vectors, and unmask the timer/keyboard lines.
+    idt::init();
+    unsafe {
+        pic::remap();
+        core::arch::asm!("sti");
     }
-    
-    // Simulate a key press
-    let key_event = dispatcher.create_event(EventType::KeyPress, 8);
-    dispatcher.dispatch_event(key_event);
-    
-    // Shut down the system
-    let shutdown_event = dispatcher.create_event(EventType::SystemShutdown, 10);
-    dispatcher.dispatch_event(shutdown_event);
-    
-    // In a real OS, we would power off the machine here
+
+    // Idle until an interrupt wakes us, then drain whatever it queued.
     loop {
-        // Halt the CPU until the next interrupt
         unsafe {
             core::arch::asm!("hlt");
         }
+        dispatcher.run();
     }
 }
 
+// Set while `panic` is rendering a diagnostic, so a second panic triggered
+// by the rendering path itself (e.g. a broken console) doesn't recurse.
+static PANICKING: AtomicBool = AtomicBool::new(false);
+
 // Required panic handler
 #[panic_handler]
-fn panic(_info: &PanicInfo) -> ! {
-    // In a real kernel, we would log the panic and possibly display it
+fn panic(info: &PanicInfo) -> ! {
+    if !PANICKING.swap(true, Ordering::SeqCst) {
+        // `PanicInfo`'s `Display` impl already includes the message plus the
+        // file/line location, so this is the whole diagnostic.
+        let message = alloc::format!("{}", info);
+        if let Some(dispatcher) = unsafe { (*core::ptr::addr_of_mut!(DISPATCHER)).as_mut() } {
+            dispatcher.console.panic_screen(&message);
+            // So the panic is visible on a headless/serial-only run too, not
+            // just whatever is watching the screen.
+            if let Some(serial) = &mut dispatcher.serial {
+                serial.write_str(&message);
+            }
+        }
+    }
     loop {
         core::hint::spin_loop();
     }
+}
+
+// Called when the global allocator can't satisfy an allocation.
+#[alloc_error_handler]
+fn alloc_error(layout: Layout) -> ! {
+    panic!(
+        "allocation of {} bytes (align {}) failed",
+        layout.size(),
+        layout.align()
+    );
 }
\ No newline at end of file
@@ -0,0 +1,70 @@
+// Remaps and drives the legacy dual 8259 PIC. By default the BIOS/UEFI maps
+// IRQ0-7 onto interrupt vectors 0x08-0x0F, which collide with the CPU's own
+// exception vectors, so the first thing we do after taking over the IDT is
+// move them somewhere safe.
+
+use crate::port::{inb, outb};
+
+const PIC1_COMMAND: u16 = 0x20;
+const PIC1_DATA: u16 = 0x21;
+const PIC2_COMMAND: u16 = 0xA0;
+const PIC2_DATA: u16 = 0xA1;
+
+const ICW1_ICW4: u8 = 0x01; // ICW4 will be present
+const ICW1_INIT: u8 = 0x10; // initialization request
+const ICW4_8086: u8 = 0x01; // 8086/88 (MCS-80/85) mode
+
+/// Vector the master PIC's IRQ0 is remapped to.
+pub const PIC1_OFFSET: u8 = 0x20;
+/// Vector the slave PIC's IRQ8 is remapped to.
+pub const PIC2_OFFSET: u8 = 0x28;
+
+// Writing to the unused port 0x80 takes long enough to act as a delay the
+// PIC needs between the command bytes of its init sequence on real hardware.
+unsafe fn io_wait() {
+    unsafe { outb(0x80, 0) };
+}
+
+/// Remap the PIC's IRQs to `PIC1_OFFSET`/`PIC2_OFFSET` and unmask the timer
+/// (IRQ0) and keyboard (IRQ1) lines, leaving every other line in whatever
+/// state the firmware left it in.
+pub unsafe fn remap() {
+    unsafe {
+        let saved_mask1 = inb(PIC1_DATA);
+        let saved_mask2 = inb(PIC2_DATA);
+
+        outb(PIC1_COMMAND, ICW1_INIT | ICW1_ICW4);
+        io_wait();
+        outb(PIC2_COMMAND, ICW1_INIT | ICW1_ICW4);
+        io_wait();
+
+        outb(PIC1_DATA, PIC1_OFFSET);
+        io_wait();
+        outb(PIC2_DATA, PIC2_OFFSET);
+        io_wait();
+
+        outb(PIC1_DATA, 4); // tell the master PIC there's a slave at IRQ2
+        io_wait();
+        outb(PIC2_DATA, 2); // tell the slave PIC its cascade identity
+        io_wait();
+
+        outb(PIC1_DATA, ICW4_8086);
+        io_wait();
+        outb(PIC2_DATA, ICW4_8086);
+        io_wait();
+
+        // Restore the previous masks, then unmask IRQ0 and IRQ1 on the master.
+        outb(PIC1_DATA, saved_mask1 & !0b0000_0011);
+        outb(PIC2_DATA, saved_mask2);
+    }
+}
+
+/// Signal end-of-interrupt for the given IRQ line (0-15).
+pub unsafe fn send_eoi(irq: u8) {
+    unsafe {
+        if irq >= 8 {
+            outb(PIC2_COMMAND, 0x20);
+        }
+        outb(PIC1_COMMAND, 0x20);
+    }
+}
@@ -0,0 +1,19 @@
+// Thin wrappers around the `in`/`out` instructions used to talk to legacy
+// x86 I/O ports (UART, PIC, PS/2 controller, ...). Kept in one place so
+// every port-mapped driver shares the same inline asm.
+
+/// Write a byte to an I/O port.
+pub unsafe fn outb(port: u16, value: u8) {
+    unsafe {
+        core::arch::asm!("out dx, al", in("dx") port, in("al") value, options(nomem, nostack, preserves_flags));
+    }
+}
+
+/// Read a byte from an I/O port.
+pub unsafe fn inb(port: u16) -> u8 {
+    let value: u8;
+    unsafe {
+        core::arch::asm!("in al, dx", out("al") value, in("dx") port, options(nomem, nostack, preserves_flags));
+    }
+    value
+}
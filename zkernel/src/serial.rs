@@ -0,0 +1,73 @@
+// A console backend that drives the 16550 UART on COM1 (I/O port 0x3F8).
+// Unlike the VGA text buffer, serial output is visible when the kernel runs
+// headless under QEMU (`-serial stdio`), which makes it useful for boot logs
+// and CI.
+
+use crate::port::{inb, outb};
+use crate::TextDisplay;
+
+const COM1: u16 = 0x3F8;
+
+struct Ports;
+
+impl Ports {
+    const DATA: u16 = COM1;
+    const INTERRUPT_ENABLE: u16 = COM1 + 1;
+    const DIVISOR_LOW: u16 = COM1;
+    const DIVISOR_HIGH: u16 = COM1 + 1;
+    const FIFO_CONTROL: u16 = COM1 + 2;
+    const LINE_CONTROL: u16 = COM1 + 3;
+    const LINE_STATUS: u16 = COM1 + 5;
+}
+
+const LINE_STATUS_TRANSMIT_EMPTY: u8 = 1 << 5;
+
+pub struct SerialConsole;
+
+impl SerialConsole {
+    pub fn new() -> Self {
+        unsafe {
+            outb(Ports::INTERRUPT_ENABLE, 0x00); // disable interrupts
+            outb(Ports::LINE_CONTROL, 0x80); // enable DLAB to set the baud rate divisor
+            outb(Ports::DIVISOR_LOW, 0x01); // divisor 1 => 115200 baud
+            outb(Ports::DIVISOR_HIGH, 0x00);
+            outb(Ports::LINE_CONTROL, 0x03); // 8 bits, no parity, one stop bit (8N1), DLAB off
+            outb(Ports::FIFO_CONTROL, 0xC7); // enable FIFO, clear it, 14-byte threshold
+        }
+        Self
+    }
+
+    fn line_status(&self) -> u8 {
+        unsafe { inb(Ports::LINE_STATUS) }
+    }
+
+    fn write_byte(&mut self, byte: u8) {
+        while self.line_status() & LINE_STATUS_TRANSMIT_EMPTY == 0 {
+            core::hint::spin_loop();
+        }
+        unsafe { outb(Ports::DATA, byte) };
+    }
+}
+
+impl TextDisplay for SerialConsole {
+    fn clear(&mut self) {
+        // A serial terminal has no addressable screen to clear; leave a
+        // visual separator instead so boot logs stay readable.
+        self.write_str("\n\r");
+    }
+
+    fn write_char(&mut self, c: char) {
+        match c {
+            '\n' => self.write_byte(b'\n'),
+            '\r' => self.write_byte(b'\r'),
+            _ if c.is_ascii() => self.write_byte(c as u8),
+            _ => self.write_byte(b'?'),
+        }
+    }
+
+    fn write_str(&mut self, s: &str) {
+        for c in s.chars() {
+            self.write_char(c);
+        }
+    }
+}
@@ -0,0 +1,32 @@
+// `zkernel`'s `TextDisplay` glue over the shared `gfx_console` VGA engine
+// (see `main.rs`'s `mod vga_engine` for how that's pulled in).
+
+use vga_engine::VgaConsole as Engine;
+
+pub use vga_engine::VgaConsole;
+
+use crate::vga_engine;
+use crate::TextDisplay;
+
+impl TextDisplay for VgaConsole {
+    fn clear(&mut self) {
+        Engine::clear(self);
+    }
+
+    fn write_char(&mut self, c: char) {
+        Engine::write_char(self, c);
+    }
+
+    fn write_str(&mut self, s: &str) {
+        Engine::write_str(self, s);
+    }
+}
+
+impl VgaConsole {
+    // Paint a full-screen diagnostic in the panic attribute (white on red)
+    // and print `message`. Used only from the panic handler.
+    pub fn panic_screen(&mut self, message: &str) {
+        Engine::panic_mode(self);
+        Engine::write_str(self, message);
+    }
+}